@@ -2,6 +2,7 @@
 
 use slab::Slab;
 use std::cmp;
+use std::mem;
 use std::ops::{Index, IndexMut};
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
@@ -19,16 +20,16 @@ impl Pointer {
     }
 }
 
-impl Index<Pointer> for RedBlackTree {
-    type Output = Node;
+impl<K, V> Index<Pointer> for RedBlackTree<K, V> {
+    type Output = Node<K, V>;
 
-    fn index(&self, index: Pointer) -> &Node {
+    fn index(&self, index: Pointer) -> &Node<K, V> {
         &self.slab[index.0]
     }
 }
 
-impl IndexMut<Pointer> for RedBlackTree {
-    fn index_mut(&mut self, index: Pointer) -> &mut Node {
+impl<K, V> IndexMut<Pointer> for RedBlackTree<K, V> {
+    fn index_mut(&mut self, index: Pointer) -> &mut Node<K, V> {
         &mut self.slab[index.0]
     }
 }
@@ -40,20 +41,33 @@ pub enum Color {
 }
 
 #[derive(Debug)]
-pub struct Node {
-    pub value: u32,
+pub struct Node<K, V> {
+    pub key: K,
+    pub value: V,
     pub right: Pointer,
     pub left: Pointer,
     pub parent: Pointer,
     pub color: Color,
+    /// Total multiplicity in the subtree rooted here (sum of `count` over
+    /// every node in it, itself included).
+    pub size: usize,
+    /// Multiplicity of `key`: 1 when the node is created, raised by each
+    /// repeat [`RedBlackTree::insert_multi`] and lowered by a single-occurrence
+    /// removal.
+    pub count: usize,
 }
 
-pub struct RedBlackTree {
-    pub slab: Slab<Node>,
+enum InsertOutcome<V> {
+    Replaced(V),
+    Inserted(Pointer),
+}
+
+pub struct RedBlackTree<K, V> {
+    pub slab: Slab<Node<K, V>>,
     pub root: Pointer,
 }
 
-impl RedBlackTree {
+impl<K: Ord, V> RedBlackTree<K, V> {
     pub fn new() -> Self {
         RedBlackTree {
             slab: Slab::new(),
@@ -94,23 +108,152 @@ impl RedBlackTree {
         count
     }
 
-    pub fn insert(&mut self, val: u32) {
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         if self.root.is_null() {
             self.root = Pointer(self.slab.insert(Node {
-                value: val,
+                key,
+                value,
                 right: Pointer::null(),
                 left: Pointer::null(),
                 parent: Pointer::null(),
                 color: Color::Black,
+                size: 1,
+                count: 1,
             }));
+            None
         } else {
-            let new_node = self.insert_node(val, self.root);
-            if !new_node.is_null() {
-                self.insert_fixup(new_node);
+            match self.insert_node(key, value, self.root) {
+                InsertOutcome::Replaced(old) => Some(old),
+                InsertOutcome::Inserted(new_node) => {
+                    self.insert_fixup(new_node);
+                    None
+                }
             }
         }
     }
 
+    /// Multiset-style insert: a fresh key is stored with multiplicity 1,
+    /// while re-inserting an existing key just bumps its multiplicity
+    /// (the first-inserted value is kept). Returns the key's multiplicity
+    /// after the insert.
+    pub fn insert_multi(&mut self, key: K, value: V) -> usize {
+        let existing = self.choose_node(self.root, &key);
+        if existing.is_null() {
+            self.insert(key, value);
+            1
+        } else {
+            self[existing].count += 1;
+            self.increment_sizes(existing);
+            self[existing].count
+        }
+    }
+
+    /// Returns how many times `key` is present (0 if it isn't).
+    pub fn count(&self, key: &K) -> usize {
+        let node = self.choose_node(self.root, key);
+        if node.is_null() {
+            0
+        } else {
+            self[node].count
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size_of(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    /// Returns the `k`-th smallest key (0-indexed), or `None` if the tree
+    /// has fewer than `k + 1` entries.
+    pub fn select(&self, k: usize) -> Option<&K> {
+        self.select_node(self.root, k).map(|node| &self[node].key)
+    }
+
+    fn select_node(&self, node: Pointer, k: usize) -> Option<Pointer> {
+        if node.is_null() {
+            return None;
+        }
+        let left_size = self.size_of(self[node].left);
+        let count = self[node].count;
+        if k < left_size {
+            self.select_node(self[node].left, k)
+        } else if k < left_size + count {
+            Some(node)
+        } else {
+            self.select_node(self[node].right, k - left_size - count)
+        }
+    }
+
+    /// Returns how many keys in the tree are strictly less than `key`,
+    /// counting each key by its multiplicity.
+    pub fn rank(&self, key: &K) -> usize {
+        self.rank_below(self.root, key)
+    }
+
+    fn rank_below(&self, node: Pointer, key: &K) -> usize {
+        if node.is_null() {
+            return 0;
+        }
+        if &self[node].key < key {
+            self[node].count
+                + self.size_of(self[node].left)
+                + self.rank_below(self[node].right, key)
+        } else {
+            self.rank_below(self[node].left, key)
+        }
+    }
+
+    /// Removes the `k`-th smallest key (0-indexed) in one pass. If that key
+    /// has multiplicity greater than 1, only one occurrence is removed and
+    /// the rest stay behind, which requires cloning the key/value out.
+    pub fn remove_nth(&mut self, k: usize) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let node = self.select_node(self.root, k)?;
+        if self[node].count > 1 {
+            self[node].count -= 1;
+            self.decrement_sizes(node, 1);
+            Some((self[node].key.clone(), self[node].value.clone()))
+        } else {
+            Some(self.remove_node(node))
+        }
+    }
+
+    fn size_of(&self, node: Pointer) -> usize {
+        if node.is_null() {
+            0
+        } else {
+            self[node].size
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let node = self.choose_node(self.root, key);
+        if node.is_null() {
+            None
+        } else {
+            Some(&self[node].value)
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = self.choose_node(self.root, key);
+        if node.is_null() {
+            None
+        } else {
+            Some(&mut self[node].value)
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        !self.choose_node(self.root, key).is_null()
+    }
+
     fn insert_fixup(&mut self, node: Pointer) {
         let parent = self[node].parent;
         if self[node].parent.is_null() {
@@ -167,15 +310,15 @@ impl RedBlackTree {
 
         if !parent_right.is_null()
             && !grandparent_left.is_null()
-            && (self[n].value == self[parent_right].value)
-            && (self[parent].value == self[grandparent_left].value)
+            && n == parent_right
+            && parent == grandparent_left
         {
             self.rotate_left(parent);
             n = self[n].left;
         } else if !parent_left.is_null()
             && !grandparent_right.is_null()
-            && (self[n].value == self[parent_left].value)
-            && (self[parent].value == self[grandparent_right].value)
+            && n == parent_left
+            && parent == grandparent_right
         {
             self.rotate_right(parent);
             n = self[n].right;
@@ -186,7 +329,7 @@ impl RedBlackTree {
 
         let parent_left = self[parent].left;
 
-        if !parent_left.is_null() && self[n].value == self[parent_left].value {
+        if !parent_left.is_null() && n == parent_left {
             self.rotate_right(grandparent);
         } else {
             self.rotate_left(grandparent);
@@ -215,44 +358,64 @@ impl RedBlackTree {
             return Pointer::null();
         }
 
-        if self[parent].value == self[grandparent_left].value {
+        if parent == grandparent_left {
             return grandparent_right;
         }
 
         return grandparent_left;
     }
 
-    fn insert_node(&mut self, val: u32, node: Pointer) -> Pointer {
-        let node_value = self[node].value;
-        let left = self[node].left;
-        let right = self[node].right;
-
-        if val == node_value {
-            return Pointer::null();
-        } else if val > node_value {
-            if right.is_null() {
-                self[node].right = Pointer(self.slab.insert(Node {
-                    value: val,
-                    right: Pointer::null(),
-                    left: Pointer::null(),
-                    parent: node,
-                    color: Color::Red,
-                }));
-                return self[node].right;
-            } else {
-                return self.insert_node(val, right);
+    fn insert_node(&mut self, key: K, value: V, node: Pointer) -> InsertOutcome<V> {
+        match key.cmp(&self[node].key) {
+            cmp::Ordering::Equal => {
+                InsertOutcome::Replaced(mem::replace(&mut self[node].value, value))
+            }
+            cmp::Ordering::Greater => {
+                let right = self[node].right;
+                let outcome = if right.is_null() {
+                    let new_node = Pointer(self.slab.insert(Node {
+                        key,
+                        value,
+                        right: Pointer::null(),
+                        left: Pointer::null(),
+                        parent: node,
+                        color: Color::Red,
+                        size: 1,
+                        count: 1,
+                    }));
+                    self[node].right = new_node;
+                    InsertOutcome::Inserted(new_node)
+                } else {
+                    self.insert_node(key, value, right)
+                };
+                if matches!(outcome, InsertOutcome::Inserted(_)) {
+                    self[node].size += 1;
+                }
+                outcome
+            }
+            cmp::Ordering::Less => {
+                let left = self[node].left;
+                let outcome = if left.is_null() {
+                    let new_node = Pointer(self.slab.insert(Node {
+                        key,
+                        value,
+                        right: Pointer::null(),
+                        left: Pointer::null(),
+                        parent: node,
+                        color: Color::Red,
+                        size: 1,
+                        count: 1,
+                    }));
+                    self[node].left = new_node;
+                    InsertOutcome::Inserted(new_node)
+                } else {
+                    self.insert_node(key, value, left)
+                };
+                if matches!(outcome, InsertOutcome::Inserted(_)) {
+                    self[node].size += 1;
+                }
+                outcome
             }
-        } else if left.is_null() {
-            self[node].left = Pointer(self.slab.insert(Node {
-                value: val,
-                right: Pointer::null(),
-                left: Pointer::null(),
-                parent: node,
-                color: Color::Red,
-            }));
-            return self[node].left;
-        } else {
-            return self.insert_node(val, left);
         }
     }
 
@@ -283,12 +446,18 @@ impl RedBlackTree {
             let parent_right = self[parent].right;
             if parent_right.is_null() {
                 self[parent].left = right;
-            } else if self[parent_right].value == self[current].value {
+            } else if parent_right == current {
                 self[parent].right = right;
             } else {
                 self[parent].left = right;
             }
         }
+
+        self[current].size = self[current].count
+            + self.size_of(self[current].left)
+            + self.size_of(self[current].right);
+        self[right].size =
+            self[right].count + self.size_of(self[right].left) + self.size_of(self[right].right);
     }
 
     fn rotate_right(&mut self, current: Pointer) {
@@ -318,50 +487,394 @@ impl RedBlackTree {
             let parent_left = self[parent].left;
             if parent_left.is_null() {
                 self[parent].right = left;
-            } else if self[parent_left].value == self[current].value {
+            } else if parent_left == current {
                 self[parent].left = left;
             } else {
                 self[parent].right = left;
             }
         }
+
+        self[current].size = self[current].count
+            + self.size_of(self[current].left)
+            + self.size_of(self[current].right);
+        self[left].size =
+            self[left].count + self.size_of(self[left].left) + self.size_of(self[left].right);
+    }
+
+    /// Removes a single occurrence of `key`, decrementing its multiplicity.
+    /// The key (and its value) are only dropped once the multiplicity
+    /// reaches zero, so plain `insert`/`get` callers — who never touch
+    /// [`RedBlackTree::insert_multi`] and so only ever see multiplicity one
+    /// — see this behave exactly like a full delete. Use
+    /// [`RedBlackTree::remove_all`] to drop every occurrence in one call.
+    /// Requires `V: Clone` because a decrement-only call still has to hand
+    /// back the value without removing it from the tree.
+    pub fn remove(&mut self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let node = self.choose_node(self.root, key);
+        if node.is_null() {
+            return None;
+        }
+        if self[node].count > 1 {
+            self[node].count -= 1;
+            self.decrement_sizes(node, 1);
+            Some(self[node].value.clone())
+        } else {
+            let (_, value) = self.remove_node(node);
+            Some(value)
+        }
+    }
+
+    /// Removes `key` entirely, regardless of its multiplicity. Use
+    /// [`RedBlackTree::remove`] to drop a single occurrence instead.
+    pub fn remove_all(&mut self, key: &K) -> Option<V> {
+        let node = self.choose_node(self.root, key);
+        if node.is_null() {
+            None
+        } else {
+            let (_, value) = self.remove_node(node);
+            Some(value)
+        }
+    }
+
+    /// Removes a single occurrence of `key`, decrementing its multiplicity.
+    /// Equivalent to [`RedBlackTree::remove`] but doesn't require `V:
+    /// Clone`, for callers that only care whether `key` was present.
+    pub fn remove_multi(&mut self, key: &K) -> bool {
+        let node = self.choose_node(self.root, key);
+        if node.is_null() {
+            return false;
+        }
+        if self[node].count > 1 {
+            self[node].count -= 1;
+            self.decrement_sizes(node, 1);
+        } else {
+            self.remove_node(node);
+        }
+        true
+    }
+
+    fn remove_node(&mut self, node: Pointer) -> (K, V) {
+        let left = self[node].left;
+        let right = self[node].right;
+
+        if !left.is_null() && !right.is_null() {
+            let mut successor = right;
+            while !self[successor].left.is_null() {
+                successor = self[successor].left;
+            }
+            self.swap_contents(node, successor);
+            return self.remove_node(successor);
+        }
+
+        let child = if !left.is_null() { left } else { right };
+        let parent = self[node].parent;
+        let is_left = !parent.is_null() && self[parent].left == node;
+        let color = self[node].color.clone();
+
+        self.transplant(node, child);
+
+        if color == Color::Black {
+            self.delete_fixup(child, parent, is_left);
+        }
+
+        // `node` is always a child of `parent` (if `node` was the root,
+        // `parent` is null and the walk does nothing: the child that took
+        // its place already has an accurate size), so a
+        // single bottom-up walk from `parent` picks up every size the
+        // transplant and fixup's rotations touched, whether `node` was a
+        // plain leaf or stood in for a swapped-in successor above it.
+        self.recompute_sizes_to_root(parent);
+
+        let removed = self.slab.remove(node.0);
+        (removed.key, removed.value)
+    }
+
+    fn swap_contents(&mut self, a: Pointer, b: Pointer) {
+        // SAFETY: `a` and `b` are distinct slab indices (`b` is strictly
+        // below `a` in the tree), so the two raw pointers never alias.
+        unsafe {
+            let pa: *mut Node<K, V> = &mut self[a];
+            let pb: *mut Node<K, V> = &mut self[b];
+            mem::swap(&mut (*pa).key, &mut (*pb).key);
+            mem::swap(&mut (*pa).value, &mut (*pb).value);
+            mem::swap(&mut (*pa).count, &mut (*pb).count);
+        }
+    }
+
+    fn transplant(&mut self, node: Pointer, child: Pointer) {
+        let parent = self[node].parent;
+        if parent.is_null() {
+            self.root = child;
+        } else if self[parent].left == node {
+            self[parent].left = child;
+        } else {
+            self[parent].right = child;
+        }
+        if !child.is_null() {
+            self[child].parent = parent;
+        }
+    }
+
+    fn increment_sizes(&mut self, mut node: Pointer) {
+        while !node.is_null() {
+            self[node].size += 1;
+            node = self[node].parent;
+        }
+    }
+
+    fn decrement_sizes(&mut self, mut node: Pointer, amount: usize) {
+        while !node.is_null() {
+            self[node].size -= amount;
+            node = self[node].parent;
+        }
+    }
+
+    /// Refreshes `size` bottom-up from `node` to the root, recomputing each
+    /// node from its (already-accurate) children rather than tracking an
+    /// adjustment amount. Used after a splice that may have swapped a
+    /// successor's key/count into an ancestor position, where a plain
+    /// decrement-by-amount can't tell how much each level on the way up
+    /// actually lost.
+    fn recompute_sizes_to_root(&mut self, mut node: Pointer) {
+        while !node.is_null() {
+            self[node].size =
+                self[node].count + self.size_of(self[node].left) + self.size_of(self[node].right);
+            node = self[node].parent;
+        }
     }
-    pub fn remove(&mut self, val: u32) {
-        if !self.get_node(val).is_null() {
-            self.remove_cheat(val);
+
+    fn color_of(&self, node: Pointer) -> Color {
+        if node.is_null() {
+            Color::Black
+        } else {
+            self[node].color.clone()
         }
     }
 
-    fn remove_cheat(&mut self, val: u32) {
-        let mut new_tree = RedBlackTree::new();
-        for i in 0..self.slab.len() {
-            if self.slab[i].value != val {
-                new_tree.insert(self.slab[i].value);
+    fn delete_fixup(&mut self, mut x: Pointer, mut parent: Pointer, mut x_is_left: bool) {
+        while !parent.is_null() && self.color_of(x) == Color::Black {
+            if x_is_left {
+                let mut sibling = self[parent].right;
+
+                if self.color_of(sibling) == Color::Red {
+                    self[sibling].color = Color::Black;
+                    self[parent].color = Color::Red;
+                    self.rotate_left(parent);
+                    sibling = self[parent].right;
+                }
+
+                let sib_left = self[sibling].left;
+                let sib_right = self[sibling].right;
+
+                if self.color_of(sib_left) == Color::Black
+                    && self.color_of(sib_right) == Color::Black
+                {
+                    self[sibling].color = Color::Red;
+                    let grandparent = self[parent].parent;
+                    x_is_left = !grandparent.is_null() && self[grandparent].left == parent;
+                    x = parent;
+                    parent = grandparent;
+                } else {
+                    if self.color_of(sib_right) == Color::Black {
+                        if !sib_left.is_null() {
+                            self[sib_left].color = Color::Black;
+                        }
+                        self[sibling].color = Color::Red;
+                        self.rotate_right(sibling);
+                        sibling = self[parent].right;
+                    }
+                    self[sibling].color = self[parent].color.clone();
+                    self[parent].color = Color::Black;
+                    let sib_right = self[sibling].right;
+                    if !sib_right.is_null() {
+                        self[sib_right].color = Color::Black;
+                    }
+                    self.rotate_left(parent);
+                    x = self.root;
+                    parent = Pointer::null();
+                }
+            } else {
+                let mut sibling = self[parent].left;
+
+                if self.color_of(sibling) == Color::Red {
+                    self[sibling].color = Color::Black;
+                    self[parent].color = Color::Red;
+                    self.rotate_right(parent);
+                    sibling = self[parent].left;
+                }
+
+                let sib_left = self[sibling].left;
+                let sib_right = self[sibling].right;
+
+                if self.color_of(sib_left) == Color::Black
+                    && self.color_of(sib_right) == Color::Black
+                {
+                    self[sibling].color = Color::Red;
+                    let grandparent = self[parent].parent;
+                    x_is_left = !grandparent.is_null() && self[grandparent].left == parent;
+                    x = parent;
+                    parent = grandparent;
+                } else {
+                    if self.color_of(sib_left) == Color::Black {
+                        if !sib_right.is_null() {
+                            self[sib_right].color = Color::Black;
+                        }
+                        self[sibling].color = Color::Red;
+                        self.rotate_left(sibling);
+                        sibling = self[parent].left;
+                    }
+                    self[sibling].color = self[parent].color.clone();
+                    self[parent].color = Color::Black;
+                    let sib_left = self[sibling].left;
+                    if !sib_left.is_null() {
+                        self[sib_left].color = Color::Black;
+                    }
+                    self.rotate_right(parent);
+                    x = self.root;
+                    parent = Pointer::null();
+                }
             }
         }
-        self.slab = new_tree.slab;
-        self.root = new_tree.root;
+
+        if !x.is_null() {
+            self[x].color = Color::Black;
+        }
+    }
+
+    /// Checks the red-black invariants: the root is black, no red node has
+    /// a red child, and every root-to-leaf path carries the same black
+    /// height. An O(n) tree walk, so callers should stick to tests and
+    /// debugging rather than release hot paths.
+    pub fn validate(&self) -> bool {
+        if self.root.is_null() {
+            return true;
+        }
+        if self[self.root].color != Color::Black {
+            println!("red-black violation: root is not black");
+            return false;
+        }
+        self.validate_below(self.root).is_some()
     }
 
-    fn get_node(&self, val: u32) -> Pointer {
-        let node = self.choose_node(self.root, val);
+    fn validate_below(&self, node: Pointer) -> Option<u32> {
         if node.is_null() {
-            println!("no such node");
+            return Some(0);
         }
-        node
+
+        if self[node].color == Color::Red {
+            let left = self[node].left;
+            let right = self[node].right;
+            if (!left.is_null() && self[left].color == Color::Red)
+                || (!right.is_null() && self[right].color == Color::Red)
+            {
+                println!("red-black violation: red node has a red child");
+                return None;
+            }
+        }
+
+        let left_height = self.validate_below(self[node].left)?;
+        let right_height = self.validate_below(self[node].right)?;
+
+        if left_height != right_height {
+            println!("red-black violation: unequal black height");
+            return None;
+        }
+
+        Some(
+            left_height
+                + if self[node].color == Color::Black {
+                    1
+                } else {
+                    0
+                },
+        )
     }
 
-    fn choose_node(&self, node: Pointer, val: u32) -> Pointer {
+    fn choose_node(&self, node: Pointer, key: &K) -> Pointer {
         if node.is_null() {
             return Pointer::null();
         }
-        match self[node].value.cmp(&val) {
+        match self[node].key.cmp(key) {
             cmp::Ordering::Equal => node,
-            cmp::Ordering::Less => self.choose_node(self[node].right, val),
-            cmp::Ordering::Greater => self.choose_node(self[node].left, val),
+            cmp::Ordering::Less => self.choose_node(self[node].right, key),
+            cmp::Ordering::Greater => self.choose_node(self[node].left, key),
+        }
+    }
+
+    fn leftmost(&self, node: Pointer) -> Pointer {
+        if node.is_null() {
+            return Pointer::null();
+        }
+        let mut n = node;
+        while !self[n].left.is_null() {
+            n = self[n].left;
+        }
+        n
+    }
+
+    fn rightmost(&self, node: Pointer) -> Pointer {
+        if node.is_null() {
+            return Pointer::null();
+        }
+        let mut n = node;
+        while !self[n].right.is_null() {
+            n = self[n].right;
+        }
+        n
+    }
+
+    fn successor(&self, node: Pointer) -> Pointer {
+        if !self[node].right.is_null() {
+            return self.leftmost(self[node].right);
+        }
+        let mut n = node;
+        let mut parent = self[n].parent;
+        while !parent.is_null() && n == self[parent].right {
+            n = parent;
+            parent = self[parent].parent;
+        }
+        parent
+    }
+
+    fn predecessor(&self, node: Pointer) -> Pointer {
+        if !self[node].left.is_null() {
+            return self.rightmost(self[node].left);
+        }
+        let mut n = node;
+        let mut parent = self[n].parent;
+        while !parent.is_null() && n == self[parent].left {
+            n = parent;
+            parent = self[parent].parent;
+        }
+        parent
+    }
+
+    fn pointers_in_order(&self) -> Vec<Pointer> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut node = self.leftmost(self.root);
+        while !node.is_null() {
+            order.push(node);
+            node = self.successor(node);
+        }
+        order
+    }
+
+    /// Iterates over `(&key, &value)` pairs in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            tree: self,
+            front: self.leftmost(self.root),
+            back: self.rightmost(self.root),
         }
     }
 
-    pub fn print(&self) {
+    pub fn print(&self)
+    where
+        K: std::fmt::Display,
+    {
         if !&self.root.is_null() {
             let mut lines = Vec::new();
             self.print_node(&mut lines, "", self.root, false);
@@ -371,7 +884,10 @@ impl RedBlackTree {
         }
     }
 
-    fn print_node(&self, lines: &mut Vec<String>, prefix: &str, node: Pointer, is_left: bool) {
+    fn print_node(&self, lines: &mut Vec<String>, prefix: &str, node: Pointer, is_left: bool)
+    where
+        K: std::fmt::Display,
+    {
         let color_str = match self[node].color {
             Color::Red => "\x1b[31mR\x1b[0m",
             Color::Black => "\x1b[30mB\x1b[0m",
@@ -379,7 +895,7 @@ impl RedBlackTree {
         let mut line = String::new();
         line += prefix;
         line += if is_left { "├── " } else { "└── " };
-        line += &format!("{} {}", color_str, self[node].value);
+        line += &format!("{} {}", color_str, self[node].key);
         lines.push(line);
         if !&self[node].left.is_null() {
             self.print_node(
@@ -399,3 +915,256 @@ impl RedBlackTree {
         }
     }
 }
+
+/// Ascending (or, via `.rev()`, descending) in-order iterator over
+/// `(&key, &value)` pairs, walking successor/predecessor links.
+pub struct Iter<'a, K, V> {
+    tree: &'a RedBlackTree<K, V>,
+    front: Pointer,
+    back: Pointer,
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front.is_null() {
+            return None;
+        }
+        let node = self.front;
+        if node == self.back {
+            self.front = Pointer::null();
+            self.back = Pointer::null();
+        } else {
+            self.front = self.tree.successor(node);
+        }
+        Some((&self.tree[node].key, &self.tree[node].value))
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back.is_null() {
+            return None;
+        }
+        let node = self.back;
+        if node == self.front {
+            self.front = Pointer::null();
+            self.back = Pointer::null();
+        } else {
+            self.back = self.tree.predecessor(node);
+        }
+        Some((&self.tree[node].key, &self.tree[node].value))
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a RedBlackTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Consuming, ascending in-order iterator over `(key, value)` pairs.
+pub struct IntoIter<K, V> {
+    order: std::vec::IntoIter<Pointer>,
+    slab: Slab<Node<K, V>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pointer = self.order.next()?;
+        let node = self.slab.remove(pointer.0);
+        Some((node.key, node.value))
+    }
+}
+
+impl<K: Ord, V> IntoIterator for RedBlackTree<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let order = self.pointers_in_order();
+        IntoIter {
+            order: order.into_iter(),
+            slab: self.slab,
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for RedBlackTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = RedBlackTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeMap;
+
+    /// Randomized insert/remove against `BTreeMap`, validating the
+    /// red-black invariants after every mutation so a broken delete-fixup
+    /// case surfaces as a failing assertion instead of a silently
+    /// unbalanced tree.
+    #[test]
+    fn delete_fixup_matches_btreemap() {
+        for seed in 0..20u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut tree: RedBlackTree<i32, i32> = RedBlackTree::new();
+            let mut model: BTreeMap<i32, i32> = BTreeMap::new();
+            let universe = 200;
+
+            for _ in 0..500 {
+                let key = rng.gen_range(0..universe);
+                if rng.gen_bool(0.6) {
+                    assert_eq!(tree.insert(key, key * 2), model.insert(key, key * 2));
+                } else {
+                    assert_eq!(tree.remove(&key), model.remove(&key));
+                }
+                assert!(tree.validate(), "seed {seed}: red-black invariant broken");
+                assert_eq!(tree.len(), model.len(), "seed {seed}");
+            }
+        }
+    }
+
+    /// `select`/`rank`/`remove_nth` cross-checked against a sorted `Vec`
+    /// rebuilt from a plain `BTreeMap` model after every mutation.
+    #[test]
+    fn order_statistics_match_sorted_vec() {
+        for seed in 0..20u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut tree: RedBlackTree<i32, i32> = RedBlackTree::new();
+            let mut model: BTreeMap<i32, i32> = BTreeMap::new();
+            let universe = 100;
+
+            for _ in 0..300 {
+                let key = rng.gen_range(0..universe);
+                if rng.gen_bool(0.6) {
+                    tree.insert(key, key * 2);
+                    model.insert(key, key * 2);
+                } else if !model.is_empty() {
+                    let idx = rng.gen_range(0..model.len());
+                    let &nth_key = model.keys().nth(idx).unwrap();
+                    let (removed_key, _) = tree.remove_nth(idx).unwrap();
+                    assert_eq!(removed_key, nth_key, "seed {seed}");
+                    model.remove(&nth_key);
+                }
+
+                let sorted: Vec<i32> = model.keys().copied().collect();
+                for (i, &key) in sorted.iter().enumerate() {
+                    assert_eq!(tree.select(i), Some(&key), "seed {seed} idx {i}");
+                    assert_eq!(tree.rank(&key), i, "seed {seed} key {key}");
+                }
+                assert_eq!(tree.select(sorted.len()), None, "seed {seed}");
+            }
+        }
+    }
+
+    /// `iter`, its reverse, `IntoIterator` and `FromIterator` all agree with
+    /// a `BTreeMap` built from the same entries.
+    #[test]
+    fn iteration_matches_btreemap() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut model: BTreeMap<i32, i32> = BTreeMap::new();
+        for _ in 0..200 {
+            let key = rng.gen_range(0..500);
+            model.insert(key, key * 2);
+        }
+
+        let tree: RedBlackTree<i32, i32> = model.iter().map(|(&k, &v)| (k, v)).collect();
+
+        let expected: Vec<(i32, i32)> = model.iter().map(|(&k, &v)| (k, v)).collect();
+        let forward: Vec<(i32, i32)> = tree.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(forward, expected);
+
+        let backward: Vec<(i32, i32)> = tree.iter().rev().map(|(&k, &v)| (k, v)).collect();
+        let mut expected_rev = expected.clone();
+        expected_rev.reverse();
+        assert_eq!(backward, expected_rev);
+
+        let consumed: Vec<(i32, i32)> = tree.into_iter().collect();
+        assert_eq!(consumed, expected);
+    }
+
+    /// Randomized `insert_multi`/`remove_multi` against a `BTreeMap` of
+    /// multiplicities, checking `len`, `count`, `select` and `rank` after
+    /// every mutation. This exercises the two-children delete case (where
+    /// `swap_contents` moves a duplicate key's count into an ancestor
+    /// position) against keys with varying multiplicity, which is where the
+    /// subtree-size bookkeeping is easiest to get wrong.
+    #[test]
+    fn multiset_matches_btreemap_counts() {
+        for seed in 0..20u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut tree: RedBlackTree<i32, i32> = RedBlackTree::new();
+            let mut model: BTreeMap<i32, usize> = BTreeMap::new();
+            let universe = 30;
+
+            for _ in 0..400 {
+                let key = rng.gen_range(0..universe);
+                if rng.gen_bool(0.6) {
+                    tree.insert_multi(key, key * 10);
+                    *model.entry(key).or_insert(0) += 1;
+                } else if model.contains_key(&key) {
+                    assert!(tree.remove_multi(&key), "seed {seed}");
+                    let count = model.get_mut(&key).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        model.remove(&key);
+                    }
+                }
+                assert!(tree.validate(), "seed {seed}: red-black invariant broken");
+
+                let total: usize = model.values().sum();
+                assert_eq!(tree.len(), total, "seed {seed}");
+                for (&key, &count) in &model {
+                    assert_eq!(tree.count(&key), count, "seed {seed} key {key}");
+                }
+
+                let expanded: Vec<i32> = model
+                    .iter()
+                    .flat_map(|(&key, &count)| std::iter::repeat_n(key, count))
+                    .collect();
+                for (i, &key) in expanded.iter().enumerate() {
+                    assert_eq!(tree.select(i), Some(&key), "seed {seed} idx {i}");
+                }
+                for &key in model.keys() {
+                    let expected_rank = expanded.iter().take_while(|&&k| k < key).count();
+                    assert_eq!(tree.rank(&key), expected_rank, "seed {seed} key {key}");
+                }
+            }
+        }
+    }
+
+    /// `remove_nth` on a duplicate key removes one occurrence, per the
+    /// request's own example: on `[1, 1, 2]`, `remove_nth(1)` addresses the
+    /// second `1`, not the `2`.
+    #[test]
+    fn remove_nth_addresses_correct_duplicate() {
+        let mut tree: RedBlackTree<i32, ()> = RedBlackTree::new();
+        tree.insert_multi(1, ());
+        tree.insert_multi(1, ());
+        tree.insert_multi(2, ());
+
+        assert_eq!(tree.count(&1), 2);
+        let (removed_key, _) = tree.remove_nth(1).unwrap();
+        assert_eq!(removed_key, 1);
+        assert_eq!(tree.count(&1), 1);
+        assert_eq!(tree.len(), 2);
+        assert!(tree.validate());
+
+        let remaining: Vec<i32> = tree.iter().map(|(&k, _)| k).collect();
+        assert_eq!(remaining, vec![1, 2]);
+    }
+}
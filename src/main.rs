@@ -6,7 +6,7 @@ use std::io::Error;
 use redblacktree::RedBlackTree;
 
 fn main() -> Result<(), Error> {
-    let mut tree = RedBlackTree::new();
+    let mut tree: RedBlackTree<u32, ()> = RedBlackTree::new();
     loop {
         let mut input = String::new();
         println!("input: ");
@@ -27,7 +27,7 @@ fn main() -> Result<(), Error> {
 
         match command.0 {
             "insert" | "add" | "put" => {
-                tree.insert(command.1);
+                tree.insert(command.1, ());
             }
             "print" | "display" | "show" => {
                 tree.print();
@@ -39,15 +39,13 @@ fn main() -> Result<(), Error> {
                 println!("{} red nodes", tree.red_count());
             }
             "remove" | "delete" => {
-                tree.remove(command.1);
+                tree.remove(&command.1);
             }
             "exit" | "break" | "quit" => break,
             _ => {
                 println!("what?");
             }
         };
-
-        
     }
     Ok(())
 }